@@ -10,7 +10,7 @@ fn solve_sudoku(c: &mut Criterion) {
     let f = BufReader::new(f);
     let sudoku = f
         .lines()
-        .map(|l| Sudoku::from_line(&l.unwrap()).unwrap())
+        .map(|l| Sudoku::from_line(3, &l.unwrap()).unwrap())
         .take(100)
         .collect::<Vec<_>>();
 
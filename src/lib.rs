@@ -1,23 +1,110 @@
+use std::io::BufRead;
+
 use rand::prelude::*;
 
+pub mod sat;
+
+/// The characters used to represent digits 1.. in `from_line`/`to_line`, in
+/// order. Covers box orders up to 5 (25x25, the largest grid whose side
+/// still fits in this alphabet).
+const ALPHABET: &[u8] = b"123456789ABCDEFGHIJKLMNOP";
+
+/// Whether `box_size` yields a side that fits in [`ALPHABET`] and in the
+/// `u32` candidate bitmask used by [`Cell`]: 2 for 4x4 up to 5 for 25x25.
+fn is_valid_box_size(box_size: usize) -> bool {
+    (2..=5).contains(&box_size)
+}
+
+pub(crate) fn char_from_digit(d: u32) -> char {
+    ALPHABET[(d - 1) as usize] as char
+}
+
+pub(crate) fn digit_from_char(c: char) -> Option<u32> {
+    let c = c.to_ascii_uppercase();
+    ALPHABET
+        .iter()
+        .position(|&b| b as char == c)
+        .map(|ix| ix as u32 + 1)
+}
+
+/// All the `k`-sized combinations of `items`, used by the naked/hidden
+/// subset techniques to try every candidate group of cells or digits.
+fn combinations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+
+    if items.len() < k {
+        return vec![];
+    }
+
+    (0..=items.len() - k)
+        .flat_map(|i| {
+            combinations(&items[i + 1..], k - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, items[i]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+/// How hard a sudoku is to solve by hand, in increasing order: the hardest
+/// technique [`Sudoku::solve_logically`] needed to reach, or
+/// [`Difficulty::NeedsGuessing`] if human techniques can't finish it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Singles,
+    NakedPair,
+    NakedTriple,
+    HiddenPair,
+    HiddenTriple,
+    LockedCandidate,
+    XWing,
+    NeedsGuessing,
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Difficulty::Singles => "singles",
+            Difficulty::NakedPair => "naked pair",
+            Difficulty::NakedTriple => "naked triple",
+            Difficulty::HiddenPair => "hidden pair",
+            Difficulty::HiddenTriple => "hidden triple",
+            Difficulty::LockedCandidate => "locked candidate",
+            Difficulty::XWing => "x-wing",
+            Difficulty::NeedsGuessing => "needs guessing",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Cell {
-    digits: u16,
+    digits: u32,
 }
 
 impl Cell {
-    pub fn from_digit(d: u16) -> Option<Self> {
-        if d == 0 || d > 9 {
+    pub fn from_digit(d: u32, side: usize) -> Option<Self> {
+        if d == 0 || d as usize > side {
             return None;
         }
 
         Some(Cell { digits: 1 << d })
     }
 
-    pub fn all_digits() -> Self {
-        Cell {
-            digits: 0b11_1111_1110,
+    /// A cell that could still be any of the digits `1..=side`.
+    pub fn all_digits(side: usize) -> Self {
+        let mut digits = 0;
+
+        for d in 1..=side as u32 {
+            digits |= 1 << d;
         }
+
+        Cell { digits }
     }
 
     pub fn is_empty(self) -> bool {
@@ -28,53 +115,105 @@ impl Cell {
         self.digits.count_ones()
     }
 
-    pub fn first_digit(self) -> u16 {
-        15 - self.digits.leading_zeros() as u16
+    pub fn first_digit(self) -> u32 {
+        31 - self.digits.leading_zeros()
     }
 
-    pub fn has_digit(self, d: u16) -> bool {
+    pub fn has_digit(self, d: u32) -> bool {
         (self.digits >> d) & 0x1 == 1
     }
 
-    // pub fn add_digit(&mut self, d: u16) {
+    // pub fn add_digit(&mut self, d: u32) {
     //     self.digits |= 1 << d;
     // }
 
-    pub fn remove_digit(&mut self, d: u16) {
+    pub fn remove_digit(&mut self, d: u32) {
         self.digits &= !(1 << d);
     }
+
+    /// The cell that could hold any digit either `self` or `other` could.
+    pub fn union(self, other: Cell) -> Cell {
+        Cell {
+            digits: self.digits | other.digits,
+        }
+    }
 }
 
+/// A sudoku grid of box order `box_size`, i.e. made of a `box_size x
+/// box_size` grid of `box_size x box_size` boxes, for a total side of
+/// `box_size * box_size` (3 for the classic 9x9 sudoku, 2 for 4x4, 4 for
+/// 16x16, 5 for 25x25). Cells are stored flattened in row-major order.
 #[derive(Clone, PartialEq, Eq)]
 pub struct Sudoku {
-    cells: [[Cell; 9]; 9],
+    box_size: usize,
+    side: usize,
+    cells: Vec<Cell>,
 }
 
 impl Sudoku {
-    pub fn from_line(line: &str) -> Option<Self> {
-        if line.chars().count() != 81 {
+    /// Build a new sudoku of the given box order with every cell still
+    /// undetermined, or `None` if `box_size` is out of the supported range
+    /// (2 for 4x4 up to 5 for 25x25).
+    pub fn new_empty(box_size: usize) -> Option<Self> {
+        if !is_valid_box_size(box_size) {
+            return None;
+        }
+
+        let side = box_size * box_size;
+
+        Some(Sudoku {
+            box_size,
+            side,
+            cells: vec![Cell::all_digits(side); side * side],
+        })
+    }
+
+    pub fn box_size(&self) -> usize {
+        self.box_size
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Parse a sudoku of the given box order from a single line of
+    /// `side * side` characters, one per cell in row-major order, `.` for an
+    /// empty cell and a character from [`ALPHABET`](constant.ALPHABET.html)
+    /// otherwise (plain digits `1`-`9` for side <= 9, then `A`-`P` for wider
+    /// grids, e.g. `1`-`9A-G` for 16x16 or `1`-`9A-P` for 25x25).
+    pub fn from_line(box_size: usize, line: &str) -> Option<Self> {
+        if !is_valid_box_size(box_size) {
+            return None;
+        }
+
+        let side = box_size * box_size;
+
+        if line.chars().count() != side * side {
             return None;
         }
 
-        let mut cells = [[Cell { digits: 0 }; 9]; 9];
+        let mut cells = vec![Cell { digits: 0 }; side * side];
 
         for (i, c) in line.chars().enumerate() {
-            cells[i / 9][i % 9] = match c {
-                '.' => Cell::all_digits(),
-                d => Cell::from_digit(d.to_digit(10)? as u16)?,
+            cells[i] = match c {
+                '.' => Cell::all_digits(side),
+                c => Cell::from_digit(digit_from_char(c)?, side)?,
             };
         }
 
-        Some(Sudoku { cells })
+        Some(Sudoku {
+            box_size,
+            side,
+            cells,
+        })
     }
 
     pub fn to_line(&self) -> String {
         self.cells
             .iter()
-            .flat_map(|r| r.iter())
             .map(|c| {
                 if c.len() == 1 {
-                    c.first_digit().to_string().chars().next().unwrap()
+                    char_from_digit(c.first_digit())
                 } else {
                     '.'
                 }
@@ -82,38 +221,153 @@ impl Sudoku {
             .collect()
     }
 
-    /// Generate a random solvable sudoku with the given number of free cells.
-    pub fn generate_solvable(rng: &mut impl Rng, free_cells: usize) -> Option<Sudoku> {
-        if free_cells > 81 {
+    /// Parse a sudoku from the classic coordinate-triple format: a first line
+    /// with the grid's `side,side` dimensions, then one `row,column,color`
+    /// line per given cell (0-based row/column, 1-based digit, `0` meaning
+    /// empty). Cells with no line are left blank. The box order is derived
+    /// from `side`, so `side` must be a perfect square.
+    pub fn from_triples(reader: impl BufRead) -> Option<Self> {
+        let mut lines = reader.lines();
+
+        let header = lines.next()?.ok()?;
+        let mut dims = header.split(',');
+        let side: usize = dims.next()?.trim().parse().ok()?;
+        let other_side: usize = dims.next()?.trim().parse().ok()?;
+        if side != other_side || dims.next().is_some() {
             return None;
         }
 
-        let mut cells_to_choose = (0..81).collect::<Vec<_>>();
-        cells_to_choose.shuffle(rng);
+        let box_size = (side as f64).sqrt().round() as usize;
+        if box_size * box_size != side {
+            return None;
+        }
 
-        let mut sudoku = Sudoku {
-            cells: [[Cell::all_digits(); 9]; 9],
+        let mut sudoku = Sudoku::new_empty(box_size)?;
+
+        for line in lines {
+            let line = line.ok()?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut parts = line.split(',');
+            let r: usize = parts.next()?.trim().parse().ok()?;
+            let c: usize = parts.next()?.trim().parse().ok()?;
+            let d: u32 = parts.next()?.trim().parse().ok()?;
+            if r >= side || c >= side || parts.next().is_some() {
+                return None;
+            }
+
+            let ix = sudoku.ix(r, c);
+            sudoku.cells[ix] = if d == 0 {
+                Cell::all_digits(side)
+            } else {
+                Cell::from_digit(d, side)?
+            };
         }
-        .first_solution()
-        .unwrap();
+
+        Some(sudoku)
+    }
+
+    /// Generate a random solvable sudoku of the given box order with the
+    /// given number of free cells.
+    pub fn generate_solvable(rng: &mut impl Rng, box_size: usize, free_cells: usize) -> Option<Sudoku> {
+        let side = box_size * box_size;
+
+        if free_cells > side * side {
+            return None;
+        }
+
+        let mut cells_to_choose = (0..side * side).collect::<Vec<_>>();
+        cells_to_choose.shuffle(rng);
+
+        let mut sudoku = Sudoku::new_empty(box_size)?.first_solution().unwrap();
 
         for _ in 0..free_cells {
             let i = cells_to_choose.swap_remove(rng.gen_range(0, cells_to_choose.len()));
 
-            sudoku.cells[i / 9][i % 9] = Cell::all_digits();
+            sudoku.cells[i] = Cell::all_digits(side);
+        }
+
+        Some(sudoku)
+    }
+
+    /// Generate a random sudoku of the given box order with about
+    /// `target_free_cells` free cells that has exactly one solution.
+    ///
+    /// Starts from a full solved grid and tries to blank cells one at a time
+    /// in a random order, only keeping a removal if the resulting grid still
+    /// has exactly one solution. If `symmetric` is `true` cells are removed
+    /// in rotationally symmetric pairs, which is the layout most hand-made
+    /// puzzles use.
+    ///
+    /// Stops once at least `target_free_cells` cells are free or every cell
+    /// has been tried, so the result can have fewer free cells than asked
+    /// for, and in symmetric mode one more when the last removal is a pair.
+    pub fn generate_unique(
+        rng: &mut impl Rng,
+        box_size: usize,
+        target_free_cells: usize,
+        symmetric: bool,
+    ) -> Option<Sudoku> {
+        let side = box_size * box_size;
+        let total = side * side;
+
+        if target_free_cells > total {
+            return None;
+        }
+
+        let mut cells_to_choose = (0..total).collect::<Vec<_>>();
+        cells_to_choose.shuffle(rng);
+
+        let mut sudoku = Sudoku::new_empty(box_size)?.first_solution().unwrap();
+
+        let mut free_cells = 0;
+
+        while free_cells < target_free_cells {
+            let i = match cells_to_choose.pop() {
+                Some(i) => i,
+                None => break,
+            };
+
+            if sudoku.cells[i].len() > 1 {
+                // Already blanked as the symmetric mirror of an earlier `i`.
+                continue;
+            }
+
+            let positions = if symmetric && i != total - 1 - i {
+                vec![i, total - 1 - i]
+            } else {
+                vec![i]
+            };
+
+            let mut candidate = sudoku.clone();
+            for &p in &positions {
+                candidate.cells[p] = Cell::all_digits(side);
+            }
+
+            if candidate.has_unique_solution() {
+                let removed = positions
+                    .iter()
+                    .filter(|&&p| sudoku.cells[p].len() == 1)
+                    .count();
+
+                sudoku = candidate;
+                free_cells += removed;
+            }
         }
 
         Some(sudoku)
     }
 
     pub fn is_solved(&self) -> bool {
-        let is_filled = self.cells.iter().all(|r| r.iter().all(|c| c.len() == 1));
+        let is_filled = self.cells.iter().all(|c| c.len() == 1);
         if !is_filled {
             return false;
         }
 
-        let has_no_duplicates = |cells: [Cell; 9]| {
-            let mut digits_set = Cell::all_digits();
+        let has_no_duplicates = |cells: Vec<Cell>| {
+            let mut digits_set = Cell::all_digits(self.side);
 
             for cell in cells.iter() {
                 let d = cell.first_digit();
@@ -123,17 +377,22 @@ impl Sudoku {
             digits_set.is_empty()
         };
 
-        let has_valid_rows = (0..9).all(|r| has_no_duplicates(self.row(r)));
+        let has_valid_rows = (0..self.side).all(|r| has_no_duplicates(self.row(r)));
         if !has_valid_rows {
             return false;
         }
 
-        let has_valid_cols = (0..9).all(|r| has_no_duplicates(self.col(r)));
+        let has_valid_cols = (0..self.side).all(|c| has_no_duplicates(self.col(c)));
         if !has_valid_cols {
             return false;
         }
 
-        let has_valid_quad = (0..9).all(|r| has_no_duplicates(self.quad(r / 3 * 3, r % 3 * 3)));
+        let has_valid_quad = (0..self.side).all(|i| {
+            let qr = i / self.box_size * self.box_size;
+            let qc = i % self.box_size * self.box_size;
+
+            has_no_duplicates(self.quad(qr, qc))
+        });
         if !has_valid_quad {
             return false;
         }
@@ -145,12 +404,108 @@ impl Sudoku {
         self.solutions().next()
     }
 
+    /// Count how many solutions this sudoku has, stopping as soon as `max`
+    /// are found so this doesn't have to enumerate an exponential number of
+    /// completions on under-constrained grids.
+    pub fn solution_count_up_to(&self, max: usize) -> usize {
+        self.solutions().take(max).count()
+    }
+
+    /// Whether this sudoku has exactly one solution.
+    pub fn has_unique_solution(&self) -> bool {
+        self.solution_count_up_to(2) == 1
+    }
+
     pub fn solutions(&self) -> impl Iterator<Item = Sudoku> {
         SolutionsIter {
             stack: vec![self.clone()],
         }
     }
 
+    /// Solve as much of the grid as possible using only human techniques
+    /// (naked/hidden singles, naked/hidden pairs and triples, locked
+    /// candidates and X-Wing) without ever guessing. The result may still
+    /// have undetermined cells if the puzzle requires guessing to finish.
+    /// Returns `None` if the grid is impossible.
+    pub fn solve_logically(&self) -> Option<Sudoku> {
+        self.apply_techniques().map(|(sudoku, _)| sudoku)
+    }
+
+    /// Rate how hard this sudoku is to solve by hand: the hardest technique
+    /// [`solve_logically`](#method.solve_logically) had to reach for, or
+    /// [`Difficulty::NeedsGuessing`] if the human techniques can't finish it
+    /// and a branching search is unavoidable.
+    pub fn rate_difficulty(&self) -> Difficulty {
+        match self.apply_techniques() {
+            None => Difficulty::NeedsGuessing,
+            Some((sudoku, hardest)) => {
+                if sudoku.is_solved() {
+                    hardest.unwrap_or(Difficulty::Singles)
+                } else {
+                    Difficulty::NeedsGuessing
+                }
+            }
+        }
+    }
+
+    /// Repeatedly apply naked/hidden singles plus the named human
+    /// techniques, in increasing order of difficulty, until nothing changes
+    /// anymore. Returns the simplified grid together with the hardest
+    /// technique that had to fire, or `None` if a contradiction was found.
+    fn apply_techniques(&self) -> Option<(Sudoku, Option<Difficulty>)> {
+        type Technique = (Difficulty, fn(&mut Sudoku) -> bool);
+
+        let techniques: [Technique; 6] = [
+            (Difficulty::NakedPair, Sudoku::eliminate_naked_pairs),
+            (Difficulty::NakedTriple, Sudoku::eliminate_naked_triples),
+            (Difficulty::HiddenPair, Sudoku::eliminate_hidden_pairs),
+            (Difficulty::HiddenTriple, Sudoku::eliminate_hidden_triples),
+            (Difficulty::LockedCandidate, Sudoku::eliminate_locked_candidates),
+            (Difficulty::XWing, Sudoku::eliminate_xwing),
+        ];
+
+        let mut sudoku = self.clone();
+        let mut hardest = None;
+
+        loop {
+            let mut changed = false;
+
+            // simplify with naked/hidden singles alone until they settle, the
+            // same way `SolutionsIter` does, so the named techniques below
+            // never act on stale candidates a single has already claimed
+            loop {
+                match sudoku.simplified() {
+                    None => return None,
+                    Some((s, simplify_changed)) => {
+                        sudoku = s;
+                        changed = changed || simplify_changed;
+
+                        if !simplify_changed {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            for (difficulty, technique) in &techniques {
+                if technique(&mut sudoku) {
+                    changed = true;
+                    hardest = Some(hardest.map_or(*difficulty, |h: Difficulty| h.max(*difficulty)));
+                }
+            }
+
+            if sudoku.cells.iter().any(|c| c.is_empty()) {
+                return None;
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        Some((sudoku, hardest))
+    }
+
     /// Simplify the grid as much as possible by first removing all digits that
     /// cannot be in a position because they must be in another one for sure and
     /// then by searching for digits that can only be in a position. Returns
@@ -170,7 +525,7 @@ impl Sudoku {
         let mut new = self.clone();
         let mut changed = false;
 
-        for r in 0..9 {
+        for r in 0..self.side {
             let row = self.row(r);
 
             for (c, cell) in row.iter().enumerate() {
@@ -181,13 +536,15 @@ impl Sudoku {
 
                         let mut remove_d_at = |nr: usize, nc: usize| {
                             if nr != r || nc != c {
-                                new.cells[nr][nc].remove_digit(d);
+                                let ix = self.ix(nr, nc);
+
+                                new.cells[ix].remove_digit(d);
 
-                                if new.cells[nr][nc] != self.cells[nr][nc] {
+                                if new.cells[ix] != self.cells[ix] {
                                     changed = true;
                                 }
 
-                                if new.cells[nr][nc].is_empty() {
+                                if new.cells[ix].is_empty() {
                                     return None;
                                 }
                             }
@@ -197,10 +554,10 @@ impl Sudoku {
 
                         let (qr, qc) = self.quad_of(r, c);
 
-                        for i in 0..9 {
+                        for i in 0..self.side {
                             remove_d_at(r, i)?;
                             remove_d_at(i, c)?;
-                            remove_d_at(qr + i / 3, qc + i % 3)?;
+                            remove_d_at(qr + i / self.box_size, qc + i % self.box_size)?;
                         }
                     }
                     _ => continue,
@@ -216,39 +573,44 @@ impl Sudoku {
     fn find_unambiguities(&mut self) -> bool {
         let mut changed = false;
 
-        for i in 0..9 {
-            for d in 1..=9 {
-                changed = self.find_unambiguity(d, (0..9).map(|c| (i, c))) || changed;
-                changed = self.find_unambiguity(d, (0..9).map(|r| (r, i))) || changed;
-
-                let qr = i / 3 * 3;
-                let qc = i % 3 * 3;
-                changed =
-                    self.find_unambiguity(d, (0..9).map(|i| (qr + i / 3, qc + i % 3))) || changed;
+        for i in 0..self.side {
+            for d in 1..=self.side as u32 {
+                changed = self.find_unambiguity(d, (0..self.side).map(|c| (i, c))) || changed;
+                changed = self.find_unambiguity(d, (0..self.side).map(|r| (r, i))) || changed;
+
+                let qr = i / self.box_size * self.box_size;
+                let qc = i % self.box_size * self.box_size;
+                let box_size = self.box_size;
+                changed = self.find_unambiguity(
+                    d,
+                    (0..self.side).map(move |i| (qr + i / box_size, qc + i % box_size)),
+                ) || changed;
             }
         }
 
         changed
     }
 
-    fn find_unambiguity(&mut self, d: u16, rng: impl IntoIterator<Item = (usize, usize)>) -> bool {
+    fn find_unambiguity(&mut self, d: u32, rng: impl IntoIterator<Item = (usize, usize)>) -> bool {
         let mut changed = false;
         let mut digit_ix = None;
 
         for (r, c) in rng.into_iter() {
-            if !self.cells[r][c].has_digit(d) {
+            let ix = self.ix(r, c);
+
+            if !self.cells[ix].has_digit(d) {
                 continue;
             }
 
             // there's already a fixed cell with this digit therefore
             // there's nothing better we could do
-            if self.cells[r][c].len() == 1 {
+            if self.cells[ix].len() == 1 {
                 digit_ix = None;
                 break;
             }
 
             match digit_ix {
-                None => digit_ix = Some((r, c)),
+                None => digit_ix = Some(ix),
                 Some(_) => {
                     digit_ix = None;
                     break;
@@ -256,60 +618,371 @@ impl Sudoku {
             };
         }
 
-        if let Some((r, c)) = digit_ix {
-            self.cells[r][c] = Cell::from_digit(d).unwrap();
+        if let Some(ix) = digit_ix {
+            self.cells[ix] = Cell::from_digit(d, self.side).unwrap();
             changed = true;
         }
 
         changed
     }
 
-    pub fn row(&self, r: usize) -> [Cell; 9] {
-        [
-            self.cells[r][0],
-            self.cells[r][1],
-            self.cells[r][2],
-            self.cells[r][3],
-            self.cells[r][4],
-            self.cells[r][5],
-            self.cells[r][6],
-            self.cells[r][7],
-            self.cells[r][8],
-        ]
-    }
-
-    pub fn col(&self, c: usize) -> [Cell; 9] {
-        [
-            self.cells[0][c],
-            self.cells[1][c],
-            self.cells[2][c],
-            self.cells[3][c],
-            self.cells[4][c],
-            self.cells[5][c],
-            self.cells[6][c],
-            self.cells[7][c],
-            self.cells[8][c],
-        ]
+    /// All the rows, columns and boxes of the grid, each as the flat cell
+    /// indices of its `side` cells.
+    fn unit_indices(&self) -> Vec<Vec<usize>> {
+        let side = self.side;
+        let box_size = self.box_size;
+
+        (0..side)
+            .flat_map(|i| {
+                let row = (0..side).map(|c| self.ix(i, c)).collect::<Vec<_>>();
+                let col = (0..side).map(|r| self.ix(r, i)).collect::<Vec<_>>();
+
+                let qr = i / box_size * box_size;
+                let qc = i % box_size * box_size;
+                let quad = (0..side)
+                    .map(|k| self.ix(qr + k / box_size, qc + k % box_size))
+                    .collect::<Vec<_>>();
+
+                vec![row, col, quad]
+            })
+            .collect()
+    }
+
+    fn eliminate_naked_pairs(&mut self) -> bool {
+        self.eliminate_naked_subsets(2)
+    }
+
+    fn eliminate_naked_triples(&mut self) -> bool {
+        self.eliminate_naked_subsets(3)
+    }
+
+    /// Naked pairs/triples: if `k` cells of a unit share the very same `k`
+    /// candidates between them, those `k` digits must end up in those `k`
+    /// cells, so they can be removed from every other cell of the unit.
+    fn eliminate_naked_subsets(&mut self, k: usize) -> bool {
+        let mut changed = false;
+
+        for unit in self.unit_indices() {
+            let candidates = unit
+                .iter()
+                .copied()
+                .filter(|&ix| (2..=k as u32).contains(&self.cells[ix].len()))
+                .collect::<Vec<_>>();
+
+            for combo in combinations(&candidates, k) {
+                let union = combo
+                    .iter()
+                    .fold(Cell { digits: 0 }, |acc, &ix| acc.union(self.cells[ix]));
+
+                if union.len() as usize != k {
+                    continue;
+                }
+
+                for &ix in &unit {
+                    if combo.contains(&ix) {
+                        continue;
+                    }
+
+                    for d in 1..=self.side as u32 {
+                        if union.has_digit(d) && self.cells[ix].has_digit(d) {
+                            self.cells[ix].remove_digit(d);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+
+    fn eliminate_hidden_pairs(&mut self) -> bool {
+        self.eliminate_hidden_subsets(2)
+    }
+
+    fn eliminate_hidden_triples(&mut self) -> bool {
+        self.eliminate_hidden_subsets(3)
+    }
+
+    /// Hidden pairs/triples: if `k` candidates of a unit only ever appear in
+    /// the very same `k` cells, those cells can't hold anything else, so
+    /// every other candidate can be stripped from them.
+    fn eliminate_hidden_subsets(&mut self, k: usize) -> bool {
+        let mut changed = false;
+        let side = self.side;
+
+        for unit in self.unit_indices() {
+            let unresolved = unit
+                .iter()
+                .copied()
+                .filter(|&ix| self.cells[ix].len() > 1)
+                .collect::<Vec<_>>();
+
+            // Only digits that still appear somewhere in the unit can be
+            // part of a hidden subset; otherwise a digit with no candidate
+            // cells at all would trivially "fit" alongside any other digit's
+            // confinement and falsely trigger an elimination.
+            let present_digits = (1..=side as u32)
+                .filter(|&d| unresolved.iter().any(|&ix| self.cells[ix].has_digit(d)))
+                .collect::<Vec<_>>();
+
+            for digits in combinations(&present_digits, k) {
+                let cells_with_digits = unresolved
+                    .iter()
+                    .copied()
+                    .filter(|&ix| digits.iter().any(|&d| self.cells[ix].has_digit(d)))
+                    .collect::<Vec<_>>();
+
+                if cells_with_digits.len() != k {
+                    continue;
+                }
+
+                for &ix in &cells_with_digits {
+                    for d in 1..=side as u32 {
+                        if !digits.contains(&d) && self.cells[ix].has_digit(d) {
+                            self.cells[ix].remove_digit(d);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Locked candidates: pointing (if a digit's candidates within a box are
+    /// all in one row/column, it can be removed from the rest of that
+    /// row/column outside the box) and its converse, box-line reduction (if
+    /// a digit's candidates within a row/column are all in one box, it can
+    /// be removed from the rest of that box outside the row/column).
+    fn eliminate_locked_candidates(&mut self) -> bool {
+        let mut changed = false;
+        let side = self.side;
+        let box_size = self.box_size;
+
+        for bi in 0..side {
+            let qr = bi / box_size * box_size;
+            let qc = bi % box_size * box_size;
+            let box_cells = (0..side)
+                .map(|k| (qr + k / box_size, qc + k % box_size))
+                .collect::<Vec<_>>();
+
+            for d in 1..=side as u32 {
+                let positions = box_cells
+                    .iter()
+                    .copied()
+                    .filter(|&(r, c)| self.cells[self.ix(r, c)].has_digit(d))
+                    .collect::<Vec<_>>();
+
+                if positions.is_empty() {
+                    continue;
+                }
+
+                if positions.iter().all(|&(r, _)| r == positions[0].0) {
+                    let r = positions[0].0;
+                    for c in 0..side {
+                        if c >= qc && c < qc + box_size {
+                            continue;
+                        }
+
+                        let ix = self.ix(r, c);
+                        if self.cells[ix].has_digit(d) {
+                            self.cells[ix].remove_digit(d);
+                            changed = true;
+                        }
+                    }
+                }
+
+                if positions.iter().all(|&(_, c)| c == positions[0].1) {
+                    let c = positions[0].1;
+                    for r in 0..side {
+                        if r >= qr && r < qr + box_size {
+                            continue;
+                        }
+
+                        let ix = self.ix(r, c);
+                        if self.cells[ix].has_digit(d) {
+                            self.cells[ix].remove_digit(d);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        for r in 0..side {
+            for d in 1..=side as u32 {
+                let cols = (0..side)
+                    .filter(|&c| self.cells[self.ix(r, c)].has_digit(d))
+                    .collect::<Vec<_>>();
+
+                if cols.is_empty() {
+                    continue;
+                }
+
+                let quad = self.quad_of(r, cols[0]);
+                if cols.iter().all(|&c| self.quad_of(r, c) == quad) {
+                    let (qr, qc) = quad;
+                    for rr in qr..qr + box_size {
+                        for cc in qc..qc + box_size {
+                            if rr == r {
+                                continue;
+                            }
+
+                            let ix = self.ix(rr, cc);
+                            if self.cells[ix].has_digit(d) {
+                                self.cells[ix].remove_digit(d);
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for c in 0..side {
+            for d in 1..=side as u32 {
+                let rows = (0..side)
+                    .filter(|&r| self.cells[self.ix(r, c)].has_digit(d))
+                    .collect::<Vec<_>>();
+
+                if rows.is_empty() {
+                    continue;
+                }
+
+                let quad = self.quad_of(rows[0], c);
+                if rows.iter().all(|&r| self.quad_of(r, c) == quad) {
+                    let (qr, qc) = quad;
+                    for rr in qr..qr + box_size {
+                        for cc in qc..qc + box_size {
+                            if cc == c {
+                                continue;
+                            }
+
+                            let ix = self.ix(rr, cc);
+                            if self.cells[ix].has_digit(d) {
+                                self.cells[ix].remove_digit(d);
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// X-Wing: if a digit's candidates in two rows are confined to the very
+    /// same two columns, it must occupy those columns in those two rows, so
+    /// it can be eliminated from those columns in every other row (and
+    /// symmetrically for two columns confined to the same two rows).
+    fn eliminate_xwing(&mut self) -> bool {
+        let mut changed = false;
+        let side = self.side;
+
+        for d in 1..=side as u32 {
+            let row_cols = (0..side)
+                .map(|r| {
+                    (
+                        r,
+                        (0..side)
+                            .filter(|&c| self.cells[self.ix(r, c)].has_digit(d))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .filter(|(_, cols)| cols.len() == 2)
+                .collect::<Vec<_>>();
+
+            for i in 0..row_cols.len() {
+                for j in (i + 1)..row_cols.len() {
+                    let (r1, cols1) = &row_cols[i];
+                    let (r2, cols2) = &row_cols[j];
+
+                    if cols1 != cols2 {
+                        continue;
+                    }
+
+                    for &c in cols1 {
+                        for r in 0..side {
+                            if r == *r1 || r == *r2 {
+                                continue;
+                            }
+
+                            let ix = self.ix(r, c);
+                            if self.cells[ix].has_digit(d) {
+                                self.cells[ix].remove_digit(d);
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let col_rows = (0..side)
+                .map(|c| {
+                    (
+                        c,
+                        (0..side)
+                            .filter(|&r| self.cells[self.ix(r, c)].has_digit(d))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .filter(|(_, rows)| rows.len() == 2)
+                .collect::<Vec<_>>();
+
+            for i in 0..col_rows.len() {
+                for j in (i + 1)..col_rows.len() {
+                    let (c1, rows1) = &col_rows[i];
+                    let (c2, rows2) = &col_rows[j];
+
+                    if rows1 != rows2 {
+                        continue;
+                    }
+
+                    for &r in rows1 {
+                        for c in 0..side {
+                            if c == *c1 || c == *c2 {
+                                continue;
+                            }
+
+                            let ix = self.ix(r, c);
+                            if self.cells[ix].has_digit(d) {
+                                self.cells[ix].remove_digit(d);
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+
+    fn ix(&self, r: usize, c: usize) -> usize {
+        r * self.side + c
+    }
+
+    pub fn row(&self, r: usize) -> Vec<Cell> {
+        (0..self.side).map(|c| self.cells[self.ix(r, c)]).collect()
+    }
+
+    pub fn col(&self, c: usize) -> Vec<Cell> {
+        (0..self.side).map(|r| self.cells[self.ix(r, c)]).collect()
     }
 
     pub fn quad_of(&self, r: usize, c: usize) -> (usize, usize) {
-        (r / 3 * 3, c / 3 * 3)
+        (r / self.box_size * self.box_size, c / self.box_size * self.box_size)
     }
 
-    pub fn quad(&self, r: usize, c: usize) -> [Cell; 9] {
+    pub fn quad(&self, r: usize, c: usize) -> Vec<Cell> {
         let (qr, qc) = self.quad_of(r, c);
 
-        [
-            self.cells[qr][qc],
-            self.cells[qr][qc + 1],
-            self.cells[qr][qc + 2],
-            self.cells[qr + 1][qc],
-            self.cells[qr + 1][qc + 1],
-            self.cells[qr + 1][qc + 2],
-            self.cells[qr + 2][qc],
-            self.cells[qr + 2][qc + 1],
-            self.cells[qr + 2][qc + 2],
-        ]
+        (0..self.side)
+            .map(|i| self.cells[self.ix(qr + i / self.box_size, qc + i % self.box_size)])
+            .collect()
     }
 }
 
@@ -351,13 +1024,13 @@ impl Iterator for SolutionsIter {
 
             // process cells with fewest possible digits first as it's more probable
             // we'll get those right
-            let (r, c, cell) = solution
+            let (ix, cell) = solution
                 .cells
                 .iter()
                 .enumerate()
-                .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, cell)| (r, c, *cell)))
-                .filter(|(_, _, cell)| cell.len() > 1)
-                .min_by_key(|(_, _, cell)| cell.len())
+                .filter(|(_, cell)| cell.len() > 1)
+                .min_by_key(|(_, cell)| cell.len())
+                .map(|(ix, cell)| (ix, *cell))
                 .unwrap();
 
             // split the sudoku into two where one has a fixed value for the
@@ -366,12 +1039,12 @@ impl Iterator for SolutionsIter {
             // first in the stack so that it will be processed later, because
             // I think it's more likely to find a solution in the former case.
             let mut candidate = solution.clone();
-            candidate.cells[r][c] = cell;
-            candidate.cells[r][c].remove_digit(cell.first_digit());
+            candidate.cells[ix] = cell;
+            candidate.cells[ix].remove_digit(cell.first_digit());
             self.stack.push(candidate);
 
             let mut candidate = solution.clone();
-            candidate.cells[r][c] = Cell::from_digit(cell.first_digit()).unwrap();
+            candidate.cells[ix] = Cell::from_digit(cell.first_digit(), solution.side).unwrap();
             self.stack.push(candidate);
         }
     }
@@ -379,7 +1052,7 @@ impl Iterator for SolutionsIter {
 
 impl std::fmt::Debug for Sudoku {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for row in self.cells.iter() {
+        for row in self.cells.chunks(self.side) {
             for c in row.iter() {
                 write!(f, "{:?} ", c)?;
             }
@@ -390,9 +1063,56 @@ impl std::fmt::Debug for Sudoku {
     }
 }
 
+/// Pretty-prints the grid as a human-readable table with box separators, e.g.
+/// for a 4x4 sudoku (box order 2):
+///
+/// ```text
+/// +--+--+
+/// |12|34|
+/// |  |12|
+/// +--+--+
+/// |21| 4|
+/// |43|21|
+/// +--+--+
+/// ```
+///
+/// Undetermined cells are rendered as a space. This is distinct from the
+/// [`Debug`](struct.Sudoku.html) impl, which dumps the raw candidate bitmask
+/// of every cell instead.
+impl std::fmt::Display for Sudoku {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let segment = format!("{}+", "-".repeat(self.box_size));
+        let separator = format!("+{}", segment.repeat(self.box_size));
+
+        for (r, row) in self.cells.chunks(self.side).enumerate() {
+            if r % self.box_size == 0 {
+                writeln!(f, "{}", separator)?;
+            }
+
+            write!(f, "|")?;
+            for (c, cell) in row.iter().enumerate() {
+                let ch = if cell.len() == 1 {
+                    char_from_digit(cell.first_digit())
+                } else {
+                    ' '
+                };
+
+                write!(f, "{}", ch)?;
+
+                if (c + 1) % self.box_size == 0 {
+                    write!(f, "|")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "{}", separator)
+    }
+}
+
 impl std::fmt::Debug for Cell {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{:010b}", self.digits)
+        write!(f, "{:026b}", self.digits)
     }
 }
 
@@ -406,18 +1126,21 @@ mod tests {
     #[test]
     fn test_solve_1() {
         let sudoku = Sudoku::from_line(
+            3,
             ".4....179..2..8.54..6..5..8.8..7.91..5..9..3..19.6..4.3..4..7..57.1..2..928....6.",
         )
         .unwrap();
         assert!(sudoku.first_solution().unwrap().is_solved());
 
         let sudoku = Sudoku::from_line(
+            3,
             "8.2.5.7.1..7.8246..1.9.....6....18325.......91843....6.....4.2..9561.3..3.8.9.6.7",
         )
         .unwrap();
         assert!(sudoku.first_solution().unwrap().is_solved());
 
         let sudoku = Sudoku::from_line(
+            3,
             "........772.3.9..1..87.5.6.5.289.....4.5.1.9.....637.5.3.9.61..2..1.7.539........",
         )
         .unwrap();
@@ -427,18 +1150,21 @@ mod tests {
     #[test]
     fn test_solve_2() {
         let sudoku = Sudoku::from_line(
+            3,
             "2.6....49.37..9...1..7....6...58.9..7.5...8.4..9.62...9....4..1...3..49.41....2.8",
         )
         .unwrap();
         assert!(sudoku.first_solution().unwrap().is_solved());
 
         let sudoku = Sudoku::from_line(
+            3,
             ".25..7..4..1..5.2.7...2.5..5.9..48.............75..6.9..3.7...6.4.1..7..8..2..91.",
         )
         .unwrap();
         assert!(sudoku.first_solution().unwrap().is_solved());
 
         let sudoku = Sudoku::from_line(
+            3,
             "..1725....8..1...625....13..7....5.....1.6.....9....8..45....297...9..6....6483..",
         )
         .unwrap();
@@ -448,18 +1174,21 @@ mod tests {
     #[test]
     fn test_solve_3() {
         let sudoku = Sudoku::from_line(
+            3,
             ".5.2.....3....5.8.96..782......3..2.7.8...1.3.4..8......164..32.7.5....1.....9.5.",
         )
         .unwrap();
         assert!(sudoku.first_solution().unwrap().is_solved());
 
         let sudoku = Sudoku::from_line(
+            3,
             "8..2...46..79.....1.....5.....5...324.8...7.132...7.....6.....9.....32..28...6..3",
         )
         .unwrap();
         assert!(sudoku.first_solution().unwrap().is_solved());
 
         let sudoku = Sudoku::from_line(
+            3,
             "..1725....8..1....25....13..7....5.....186.....9....8..45....29....9..6....6483..",
         )
         .unwrap();
@@ -469,12 +1198,14 @@ mod tests {
     #[test]
     fn test_solve_4() {
         let sudoku = Sudoku::from_line(
+            3,
             "346795812258431697971862543129576438835214769764389251517948326493627185682153974",
         )
         .unwrap();
         assert!(sudoku.first_solution().unwrap().is_solved());
 
         let sudoku = Sudoku::from_line(
+            3,
             ".......12..8.3...........4.12.5..........47...6.......5.7...3.....62.......1.....",
         )
         .unwrap();
@@ -483,7 +1214,248 @@ mod tests {
 
     #[test]
     fn test_solvable_solution_fails_on_too_many_free_cells() {
-        assert!(Sudoku::generate_solvable(&mut XorShiftRng::from_seed([0; 16]), 82).is_none());
+        assert!(Sudoku::generate_solvable(&mut XorShiftRng::from_seed([0; 16]), 3, 82).is_none());
+    }
+
+    #[test]
+    fn test_unique_solution_fails_on_too_many_free_cells() {
+        assert!(
+            Sudoku::generate_unique(&mut XorShiftRng::from_seed([0; 16]), 3, 82, false).is_none()
+        );
+    }
+
+    #[test]
+    fn test_generate_unique_has_exactly_one_solution() {
+        let sudoku =
+            Sudoku::generate_unique(&mut XorShiftRng::from_seed([0; 16]), 3, 40, true).unwrap();
+
+        assert!(sudoku.has_unique_solution());
+    }
+
+    #[test]
+    fn test_has_unique_solution() {
+        let unique = Sudoku::from_line(
+            3,
+            "346795812258431697971862543129576438835214769764389251517948326493627185682153974",
+        )
+        .unwrap();
+        assert!(unique.has_unique_solution());
+
+        let many = Sudoku::new_empty(3).unwrap();
+        assert!(!many.has_unique_solution());
+        assert_eq!(many.solution_count_up_to(5), 5);
+    }
+
+    #[test]
+    fn test_generate_solvable_4x4() {
+        let sudoku =
+            Sudoku::generate_solvable(&mut XorShiftRng::from_seed([0; 16]), 2, 6).unwrap();
+
+        assert_eq!(sudoku.side(), 4);
+        assert!(sudoku.first_solution().unwrap().is_solved());
+    }
+
+    #[test]
+    fn test_eliminate_naked_pairs_removes_candidates_outside_pair() {
+        let side = 4;
+        let mut sudoku = Sudoku::new_empty(2).unwrap();
+
+        let pair = Cell::from_digit(1, side)
+            .unwrap()
+            .union(Cell::from_digit(2, side).unwrap());
+
+        let ix00 = sudoku.ix(0, 0);
+        let ix01 = sudoku.ix(0, 1);
+        sudoku.cells[ix00] = pair;
+        sudoku.cells[ix01] = pair;
+
+        assert!(sudoku.eliminate_naked_pairs());
+
+        let ix02 = sudoku.ix(0, 2);
+        assert!(!sudoku.cells[ix02].has_digit(1));
+        assert!(!sudoku.cells[ix02].has_digit(2));
+
+        let ix10 = sudoku.ix(1, 0);
+        assert!(!sudoku.cells[ix10].has_digit(1));
+        assert!(!sudoku.cells[ix10].has_digit(2));
+
+        assert_eq!(sudoku.cells[ix00], pair);
+        assert_eq!(sudoku.cells[ix01], pair);
+    }
+
+    #[test]
+    fn test_eliminate_hidden_pairs_strips_other_candidates_from_pair() {
+        let side = 4;
+        let mut sudoku = Sudoku::new_empty(2).unwrap();
+
+        let d1 = 1;
+        let d2 = 2;
+        let ix00 = sudoku.ix(0, 0);
+        let ix01 = sudoku.ix(0, 1);
+        let ix10 = sudoku.ix(1, 0);
+        let ix11 = sudoku.ix(1, 1);
+        let ix02 = sudoku.ix(0, 2);
+        let ix03 = sudoku.ix(0, 3);
+
+        sudoku.cells[ix00] = Cell::from_digit(d1, side)
+            .unwrap()
+            .union(Cell::from_digit(d2, side).unwrap())
+            .union(Cell::from_digit(3, side).unwrap());
+        sudoku.cells[ix01] = Cell::from_digit(d1, side)
+            .unwrap()
+            .union(Cell::from_digit(d2, side).unwrap())
+            .union(Cell::from_digit(4, side).unwrap());
+        sudoku.cells[ix10] = Cell::from_digit(3, side)
+            .unwrap()
+            .union(Cell::from_digit(4, side).unwrap());
+        sudoku.cells[ix11] = Cell::from_digit(3, side)
+            .unwrap()
+            .union(Cell::from_digit(4, side).unwrap());
+        sudoku.cells[ix02] = Cell::from_digit(3, side)
+            .unwrap()
+            .union(Cell::from_digit(4, side).unwrap());
+        sudoku.cells[ix03] = Cell::from_digit(3, side)
+            .unwrap()
+            .union(Cell::from_digit(4, side).unwrap());
+
+        assert!(sudoku.eliminate_hidden_pairs());
+
+        let pair = Cell::from_digit(d1, side)
+            .unwrap()
+            .union(Cell::from_digit(d2, side).unwrap());
+        assert_eq!(sudoku.cells[ix00], pair);
+        assert_eq!(sudoku.cells[ix01], pair);
+    }
+
+    #[test]
+    fn test_eliminate_locked_candidates_pointing() {
+        let mut sudoku = Sudoku::new_empty(3).unwrap();
+
+        for r in 1..3 {
+            for c in 0..3 {
+                let ix = sudoku.ix(r, c);
+                sudoku.cells[ix].remove_digit(5);
+            }
+        }
+
+        assert!(sudoku.eliminate_locked_candidates());
+
+        let ix = sudoku.ix(0, 5);
+        assert!(!sudoku.cells[ix].has_digit(5));
+    }
+
+    #[test]
+    fn test_eliminate_xwing_eliminates_across_rows() {
+        let mut sudoku = Sudoku::new_empty(3).unwrap();
+
+        for r in 0..2 {
+            for c in 0..9 {
+                if c != 3 && c != 6 {
+                    let ix = sudoku.ix(r, c);
+                    sudoku.cells[ix].remove_digit(7);
+                }
+            }
+        }
+
+        assert!(sudoku.eliminate_xwing());
+
+        let ix3 = sudoku.ix(2, 3);
+        assert!(!sudoku.cells[ix3].has_digit(7));
+
+        let ix6 = sudoku.ix(2, 6);
+        assert!(!sudoku.cells[ix6].has_digit(7));
+    }
+
+    #[test]
+    fn test_rate_difficulty_needs_guessing_for_an_empty_grid() {
+        let sudoku = Sudoku::new_empty(3).unwrap();
+        assert_eq!(sudoku.rate_difficulty(), Difficulty::NeedsGuessing);
+    }
+
+    #[test]
+    fn test_rate_difficulty_of_an_already_solved_grid_is_singles() {
+        let sudoku = Sudoku::from_line(
+            3,
+            "346795812258431697971862543129576438835214769764389251517948326493627185682153974",
+        )
+        .unwrap();
+
+        assert_eq!(sudoku.rate_difficulty(), Difficulty::Singles);
+    }
+
+    #[test]
+    fn test_from_triples_parses_given_cells_and_leaves_the_rest_blank() {
+        let sudoku = Sudoku::from_triples("4,4\n0,0,1\n0,1,2\n1,2,3\n0,3,0\n".as_bytes()).unwrap();
+
+        assert_eq!(sudoku.box_size(), 2);
+        assert_eq!(sudoku.side(), 4);
+
+        let ix = sudoku.ix(0, 0);
+        assert_eq!(sudoku.cells[ix], Cell::from_digit(1, 4).unwrap());
+
+        let ix = sudoku.ix(0, 1);
+        assert_eq!(sudoku.cells[ix], Cell::from_digit(2, 4).unwrap());
+
+        let ix = sudoku.ix(1, 2);
+        assert_eq!(sudoku.cells[ix], Cell::from_digit(3, 4).unwrap());
+
+        // both an explicit `0` and a cell with no line at all are blank.
+        let ix = sudoku.ix(0, 3);
+        assert!(sudoku.cells[ix].len() > 1);
+        let ix = sudoku.ix(3, 3);
+        assert!(sudoku.cells[ix].len() > 1);
+    }
+
+    #[test]
+    fn test_from_triples_rejects_malformed_input() {
+        // header isn't a square side.
+        assert!(Sudoku::from_triples("5,5\n".as_bytes()).is_none());
+
+        // header isn't square (rows != columns).
+        assert!(Sudoku::from_triples("4,9\n".as_bytes()).is_none());
+
+        // row out of range.
+        assert!(Sudoku::from_triples("4,4\n4,0,1\n".as_bytes()).is_none());
+
+        // column out of range.
+        assert!(Sudoku::from_triples("4,4\n0,4,1\n".as_bytes()).is_none());
+
+        // digit out of range.
+        assert!(Sudoku::from_triples("4,4\n0,0,5\n".as_bytes()).is_none());
+    }
+
+    #[test]
+    fn test_display_prints_box_separators_and_given_digits() {
+        let sudoku = Sudoku::from_triples("4,4\n0,0,1\n0,1,2\n1,2,3\n".as_bytes()).unwrap();
+
+        assert_eq!(
+            sudoku.to_string(),
+            "+--+--+\n\
+             |12|  |\n\
+             |  |3 |\n\
+             +--+--+\n\
+             |  |  |\n\
+             |  |  |\n\
+             +--+--+\n"
+        );
+    }
+
+    #[test]
+    fn test_solve_logically_never_contradicts_the_actual_solution() {
+        let sudoku = Sudoku::from_line(
+            3,
+            ".4....179..2..8.54..6..5..8.8..7.91..5..9..3..19.6..4.3..4..7..57.1..2..928....6.",
+        )
+        .unwrap();
+
+        let solution = sudoku.first_solution().unwrap();
+        let logically_solved = sudoku.solve_logically().unwrap();
+
+        for (cell, solved_cell) in logically_solved.cells.iter().zip(solution.cells.iter()) {
+            if cell.len() == 1 {
+                assert_eq!(cell.first_digit(), solved_cell.first_digit());
+            }
+        }
     }
 
     proptest! {
@@ -491,6 +1463,7 @@ mod tests {
         fn random_solvable_solutions_are_solvable(free_cells in 0..82_usize, seed: [u8; 16]) {
             let sudoku = Sudoku::generate_solvable(
                 &mut XorShiftRng::from_seed(seed),
+                3,
                 free_cells
             ).unwrap();
 
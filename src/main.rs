@@ -5,10 +5,49 @@ use std::path::PathBuf;
 use std::time::Instant;
 
 use rayon::prelude::*;
+use structopt::clap::arg_enum;
 use structopt::StructOpt;
 
 use sudoku_rs::Sudoku;
 
+/// structopt validator for `-b/--box-size`: rejects anything outside the
+/// range `Sudoku` actually supports (2 for 4x4 up to 5 for 25x25), so a bad
+/// value is reported as a clap usage error instead of panicking or hanging
+/// deep inside the solver.
+fn validate_box_size(s: String) -> Result<(), String> {
+    match s.parse::<usize>() {
+        Ok(b) if (2..=5).contains(&b) => Ok(()),
+        Ok(b) => Err(format!(
+            "box-size must be between 2 and 5 (got {}); sudoku-rs only supports \
+             4x4 up to 25x25 grids",
+            b
+        )),
+        Err(_) => Err(format!("'{}' isn't a valid box-size", s)),
+    }
+}
+
+arg_enum! {
+    /// Which solving backend to use for the `solve` subcommand.
+    #[derive(Debug, Clone, Copy)]
+    enum Solver {
+        Backtracking,
+        Sat,
+    }
+}
+
+arg_enum! {
+    /// Which input format the `solve` subcommand should expect. `Auto`
+    /// inspects the first line of the input and picks `Triples` if it
+    /// contains a comma, like a `side,side` dimensions header, `Line`
+    /// otherwise.
+    #[derive(Debug, Clone, Copy)]
+    enum Format {
+        Auto,
+        Line,
+        Triples,
+    }
+}
+
 /// Just a sudoku solver and generator.
 #[derive(StructOpt, Debug)]
 enum Cmd {
@@ -22,6 +61,21 @@ enum Cmd {
         /// How many sudoku to generate.
         #[structopt(short = "c", long = "count")]
         count: usize,
+
+        /// Ensure each generated sudoku has exactly one solution, carving
+        /// holes in rotationally symmetric pairs.
+        #[structopt(short = "u", long = "unique")]
+        unique: bool,
+
+        /// Box order of the grid: 2 for 4x4, 3 for the classic 9x9, 4 for
+        /// 16x16, 5 for 25x25.
+        #[structopt(
+            short = "b",
+            long = "box-size",
+            default_value = "3",
+            validator = validate_box_size
+        )]
+        box_size: usize,
     },
 
     /// Solve all the sudoku from the given file or input printing the total
@@ -36,11 +90,65 @@ enum Cmd {
         #[structopt(short = "v", long = "verbose")]
         verbose: bool,
 
+        /// Which solving backend to use.
+        #[structopt(
+            long = "solver",
+            possible_values = &Solver::variants(),
+            case_insensitive = true,
+            default_value = "Backtracking"
+        )]
+        solver: Solver,
+
+        /// Rate each sudoku's difficulty (the hardest human technique
+        /// needed) instead of solving it.
+        #[structopt(long = "rate")]
+        rate: bool,
+
+        /// Which input format to expect: `line` for one `side * side`-char
+        /// line per sudoku (81 chars for 9x9), `triples` for the classic
+        /// coordinate-triple format, or `auto` to detect it from the first
+        /// line of the input.
+        #[structopt(
+            long = "format",
+            possible_values = &Format::variants(),
+            case_insensitive = true,
+            default_value = "Auto"
+        )]
+        format: Format,
+
+        /// Print solved grids as a human-readable table instead of a
+        /// `side * side`-char line. Solved grids are only printed with
+        /// `--verbose` and without `--parallel`.
+        #[structopt(long = "pretty")]
+        pretty: bool,
+
+        /// Box order of the input grids: 2 for 4x4, 3 for the classic 9x9,
+        /// 4 for 16x16, 5 for 25x25.
+        #[structopt(
+            short = "b",
+            long = "box-size",
+            default_value = "3",
+            validator = validate_box_size
+        )]
+        box_size: usize,
+
         #[structopt(parse(from_os_str))]
         sudoku: Option<PathBuf>,
     },
 }
 
+/// The flags that shape how `solve` reads and reports on its input, grouped
+/// together so `solve_sudoku` doesn't have to take them one by one.
+struct SolveOptions {
+    box_size: usize,
+    parallel: bool,
+    verbose: bool,
+    solver: Solver,
+    rate: bool,
+    format: Format,
+    pretty: bool,
+}
+
 fn main() -> io::Result<()> {
     let cmd = Cmd::from_args();
 
@@ -48,45 +156,94 @@ fn main() -> io::Result<()> {
     let stdout = stdout.lock();
 
     match cmd {
-        Cmd::Generate { free_cells, count } => generate_sudoku(free_cells, count, stdout),
+        Cmd::Generate {
+            free_cells,
+            count,
+            unique,
+            box_size,
+        } => generate_sudoku(box_size, free_cells, count, unique, stdout),
         Cmd::Solve {
             verbose,
             parallel,
-            sudoku: Some(p),
+            solver,
+            rate,
+            format,
+            pretty,
+            box_size,
+            sudoku,
         } => {
-            let f = File::open(p)?;
-            solve_sudoku(parallel, f, stdout, verbose)
-        }
-        Cmd::Solve {
-            verbose,
-            parallel,
-            sudoku: None,
-        } => {
-            let stdin = io::stdin();
-            let stdin = stdin.lock();
-            solve_sudoku(parallel, stdin, stdout, verbose)
+            let opts = SolveOptions {
+                box_size,
+                parallel,
+                verbose,
+                solver,
+                rate,
+                format,
+                pretty,
+            };
+
+            match sudoku {
+                Some(p) => solve_sudoku(opts, File::open(p)?, stdout),
+                None => {
+                    let stdin = io::stdin();
+                    let stdin = stdin.lock();
+                    solve_sudoku(opts, stdin, stdout)
+                }
+            }
         }
     }
 }
 
-fn solve_sudoku(
-    parallel: bool,
-    r: impl Read,
-    mut out: impl Write,
-    verbose: bool,
-) -> io::Result<()> {
-    let buf = BufReader::new(r);
+/// Peek at the first line of `buf` (without consuming it) and guess whether
+/// it's a coordinate-triples dimensions header or the start of a batch of
+/// `side * side`-char lines.
+fn detect_format(buf: &mut impl BufRead) -> io::Result<Format> {
+    let bytes = buf.fill_buf()?;
+    let line_end = bytes.iter().position(|&b| b == b'\n').unwrap_or(bytes.len());
+    let first_line = String::from_utf8_lossy(&bytes[..line_end]);
+
+    Ok(if first_line.contains(',') {
+        Format::Triples
+    } else {
+        Format::Line
+    })
+}
+
+fn solve_sudoku(opts: SolveOptions, r: impl Read, mut out: impl Write) -> io::Result<()> {
+    let mut buf = BufReader::new(r);
 
-    let sudoku = buf
-        .lines()
-        .map(|l| Sudoku::from_line(&l.unwrap()).unwrap())
-        .collect::<Vec<_>>();
+    let format = match opts.format {
+        Format::Auto => detect_format(&mut buf)?,
+        format => format,
+    };
+
+    let sudoku = match format {
+        Format::Auto => unreachable!("resolved above"),
+        Format::Line => buf
+            .lines()
+            .map(|l| Sudoku::from_line(opts.box_size, &l.unwrap()).unwrap())
+            .collect::<Vec<_>>(),
+        Format::Triples => {
+            vec![Sudoku::from_triples(buf).expect("invalid coordinate-triples input")]
+        }
+    };
+
+    if opts.rate {
+        for (i, s) in sudoku.iter().enumerate() {
+            writeln!(out, "#{} difficulty: {}", i, s.rate_difficulty())?;
+        }
+
+        return Ok(());
+    }
 
     let start_t = Instant::now();
 
     let sudoku_fn = |i: usize, s: Sudoku| {
-        let solution = s.first_solution();
-        let is_solved = solution.map_or(false, |s| s.is_solved());
+        let solution = match opts.solver {
+            Solver::Backtracking => s.first_solution(),
+            Solver::Sat => sudoku_rs::sat::solve(&s),
+        };
+        let is_solved = solution.as_ref().is_some_and(|s| s.is_solved());
 
         if !is_solved {
             panic!(
@@ -95,10 +252,10 @@ fn solve_sudoku(
             );
         }
 
-        is_solved
+        solution.unwrap()
     };
 
-    match (parallel, verbose) {
+    match (opts.parallel, opts.verbose) {
         (true, _) => {
             sudoku.into_par_iter().enumerate().for_each(|(i, s)| {
                 sudoku_fn(i, s);
@@ -111,9 +268,15 @@ fn solve_sudoku(
         }
         (false, true) => {
             for (i, sudoku) in sudoku.into_iter().enumerate() {
-                let is_solved = sudoku_fn(i, sudoku);
+                let solved = sudoku_fn(i, sudoku);
+
+                writeln!(out, "#{} is solved {:?}", i, true)?;
 
-                writeln!(out, "#{} is solved {:?}", i, is_solved)?;
+                if opts.pretty {
+                    writeln!(out, "{}", solved)?;
+                } else {
+                    writeln!(out, "{}", solved.to_line())?;
+                }
             }
         }
     }
@@ -121,12 +284,23 @@ fn solve_sudoku(
     writeln!(out, "total time elapsed {:?}", start_t.elapsed())
 }
 
-fn generate_sudoku(free_cells: usize, count: usize, mut out: impl Write) -> io::Result<()> {
+fn generate_sudoku(
+    box_size: usize,
+    free_cells: usize,
+    count: usize,
+    unique: bool,
+    mut out: impl Write,
+) -> io::Result<()> {
     let mut rng = rand::thread_rng();
 
     for _ in 0..count {
-        let sudoku = Sudoku::generate_solvable(&mut rng, free_cells)
-            .expect("cannot create a solvable sudoku");
+        let sudoku = if unique {
+            Sudoku::generate_unique(&mut rng, box_size, free_cells, true)
+                .expect("cannot create a unique solvable sudoku")
+        } else {
+            Sudoku::generate_solvable(&mut rng, box_size, free_cells)
+                .expect("cannot create a solvable sudoku")
+        };
 
         writeln!(out, "{}", sudoku.to_line())?;
     }
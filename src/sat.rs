@@ -0,0 +1,133 @@
+//! Alternative solving backend that encodes a sudoku as a boolean
+//! satisfiability problem and defers to a SAT solver instead of the
+//! hand-written backtracking search in [`crate::Sudoku::solutions`].
+//!
+//! One boolean variable `x(r, c, d)` is used per cell/digit combination
+//! (`r, c` in `0..side`, `d` in `1..=side`). The variable index is
+//! `side * side * r + side * c + (d - 1)`.
+
+use varisat::{CnfFormula, ExtendFormula, Lit, Solver};
+
+use crate::{char_from_digit, Sudoku};
+
+fn var(side: usize, r: usize, c: usize, d: u32) -> usize {
+    side * side * r + side * c + (d as usize - 1)
+}
+
+fn lit(side: usize, r: usize, c: usize, d: u32, positive: bool) -> Lit {
+    Lit::from_index(var(side, r, c, d), positive)
+}
+
+/// All the units (rows, columns and boxes) of a sudoku with the given box
+/// order, as lists of their `side` cell coordinates.
+fn units(box_size: usize) -> Vec<Vec<(usize, usize)>> {
+    let side = box_size * box_size;
+
+    (0..side)
+        .flat_map(|i| {
+            let row = (0..side).map(|c| (i, c)).collect::<Vec<_>>();
+            let col = (0..side).map(|r| (r, i)).collect::<Vec<_>>();
+
+            let qr = i / box_size * box_size;
+            let qc = i % box_size * box_size;
+            let quad = (0..side)
+                .map(|k| (qr + k / box_size, qc + k % box_size))
+                .collect::<Vec<_>>();
+
+            vec![row, col, quad]
+        })
+        .collect()
+}
+
+/// Encode `sudoku` as a CNF formula: each cell has at least one digit, at
+/// most one digit, each unit (row/column/box) has every digit exactly once,
+/// and the clues already present in `sudoku` are pinned down as unit
+/// clauses.
+fn encode(sudoku: &Sudoku) -> CnfFormula {
+    let side = sudoku.side();
+    let mut formula = CnfFormula::new();
+
+    for r in 0..side {
+        for c in 0..side {
+            formula.add_clause(
+                &(1..=side as u32)
+                    .map(|d| lit(side, r, c, d, true))
+                    .collect::<Vec<_>>(),
+            );
+
+            for d1 in 1..=side as u32 {
+                for d2 in (d1 + 1)..=side as u32 {
+                    formula.add_clause(&[
+                        lit(side, r, c, d1, false),
+                        lit(side, r, c, d2, false),
+                    ]);
+                }
+            }
+        }
+    }
+
+    for unit in &units(sudoku.box_size()) {
+        for d in 1..=side as u32 {
+            formula.add_clause(
+                &unit
+                    .iter()
+                    .map(|&(r, c)| lit(side, r, c, d, true))
+                    .collect::<Vec<_>>(),
+            );
+
+            for i in 0..unit.len() {
+                for j in (i + 1)..unit.len() {
+                    let (r1, c1) = unit[i];
+                    let (r2, c2) = unit[j];
+                    formula.add_clause(&[
+                        lit(side, r1, c1, d, false),
+                        lit(side, r2, c2, d, false),
+                    ]);
+                }
+            }
+        }
+    }
+
+    for r in 0..side {
+        for (c, cell) in sudoku.row(r).iter().enumerate() {
+            if cell.len() == 1 {
+                formula.add_clause(&[lit(side, r, c, cell.first_digit(), true)]);
+            }
+        }
+    }
+
+    formula
+}
+
+/// Solve `sudoku` with a SAT solver, decoding the satisfying model back into
+/// a grid. Returns `None` if the sudoku has no solution.
+///
+/// This is dramatically faster than [`Sudoku::first_solution`] on
+/// pathological inputs that make the backtracking search thrash.
+pub fn solve(sudoku: &Sudoku) -> Option<Sudoku> {
+    let side = sudoku.side();
+    let formula = encode(sudoku);
+
+    let mut solver = Solver::new();
+    solver.add_formula(&formula);
+
+    if !solver.solve().expect("SAT solver failed") {
+        return None;
+    }
+
+    let mut line = vec!['.'; side * side];
+    for l in solver.model().expect("solver reported SAT but has no model") {
+        if !l.is_positive() {
+            continue;
+        }
+
+        let ix = l.index();
+        let r = ix / (side * side);
+        let c = (ix / side) % side;
+        let d = (ix % side) as u32 + 1;
+
+        line[r * side + c] = char_from_digit(d);
+    }
+
+    Sudoku::from_line(sudoku.box_size(), &line.into_iter().collect::<String>())
+}